@@ -0,0 +1,39 @@
+//! Bakes build metadata into the binary for `/version`: the git commit as
+//! `GIT_COMMIT_HASH` and the build time (Unix seconds) as `BUILD_TIMESTAMP`.
+//! Either is left unset when it can't be determined, and the server reports
+//! it as "unknown".
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty());
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=GIT_COMMIT_HASH={}", commit);
+    }
+
+    // Reproducible builds pin the timestamp through SOURCE_DATE_EPOCH.
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs())
+        });
+    if let Some(timestamp) = timestamp {
+        println!("cargo:rustc-env=BUILD_TIMESTAMP={}", timestamp);
+    }
+}